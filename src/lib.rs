@@ -1,6 +1,16 @@
 use bitflags::bitflags;
 use libc::{sem_t, O_CREAT, O_EXCL, S_IRWXG, S_IRWXO, S_IRWXU};
-use std::{ffi::CString, io::Error};
+use std::{
+    ffi::CString,
+    future::Future,
+    io::Error,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+pub mod sysv;
+pub use sysv::{SemOp, SysVError, SysVSemaphore};
 
 // TO SIMPLIFY THING, ONLY
 bitflags! {
@@ -32,11 +42,18 @@ bitflags! {
     }
 }
 
-// #[derive(Send, Sync)]
 pub struct NamedSemaphore {
     raw: *mut sem_t,
+    name: CString,
 }
 
+// SAFETY: `raw` is a `*mut sem_t` obtained from `sem_open`. The kernel
+// serializes all `sem_*` operations against the pointed-to semaphore, so it
+// is sound to send a `NamedSemaphore` to another thread or to share `&self`
+// across threads.
+unsafe impl Send for NamedSemaphore {}
+unsafe impl Sync for NamedSemaphore {}
+
 impl NamedSemaphore {
     pub unsafe fn create(
         name: &str,
@@ -57,7 +74,7 @@ impl NamedSemaphore {
             return Err(std::io::Error::last_os_error());
         }
 
-        Ok(Self { raw })
+        Ok(Self { raw, name })
     }
 
     pub unsafe fn open(name: &str) -> std::io::Result<Self> {
@@ -66,7 +83,7 @@ impl NamedSemaphore {
         if raw == libc::SEM_FAILED {
             return Err(std::io::Error::last_os_error());
         }
-        Ok(Self { raw })
+        Ok(Self { raw, name })
     }
 
     pub unsafe fn open_or_create(
@@ -97,6 +114,126 @@ impl NamedSemaphore {
         Ok(())
     }
 
+    /// Attempts to acquire the semaphore without blocking.
+    ///
+    /// Returns `Ok(true)` if the count was decremented, or `Ok(false)` if the
+    /// semaphore was already at zero and the call would have blocked.
+    pub unsafe fn try_wait(&self) -> std::io::Result<bool> {
+        let res = libc::sem_trywait(self.raw);
+        if res == -1 {
+            let err = Error::last_os_error();
+            if err.raw_os_error() == Some(libc::EAGAIN) {
+                return Ok(false);
+            }
+            return Err(err);
+        }
+        Ok(true)
+    }
+
+    /// Waits on the semaphore, giving up after `timeout` has elapsed.
+    ///
+    /// Returns `Ok(true)` if the count was decremented, or `Ok(false)` if
+    /// `timeout` elapsed before the semaphore could be acquired.
+    pub unsafe fn timed_wait(&self, timeout: Duration) -> std::io::Result<bool> {
+        let mut deadline = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        let res = libc::clock_gettime(libc::CLOCK_REALTIME, &mut deadline);
+        if res == -1 {
+            return Err(Error::last_os_error());
+        }
+
+        deadline.tv_sec += timeout.as_secs() as libc::time_t;
+        deadline.tv_nsec += timeout.subsec_nanos() as libc::c_long;
+        if deadline.tv_nsec >= 1_000_000_000 {
+            deadline.tv_sec += 1;
+            deadline.tv_nsec -= 1_000_000_000;
+        }
+
+        let res = libc::sem_timedwait(self.raw, &deadline);
+        if res == -1 {
+            let err = Error::last_os_error();
+            if err.raw_os_error() == Some(libc::ETIMEDOUT) {
+                return Ok(false);
+            }
+            return Err(err);
+        }
+        Ok(true)
+    }
+
+    /// Posts to the semaphore `n` times, incrementing its count by `n`.
+    ///
+    /// Stops and returns the error on the first failing `sem_post`, leaving
+    /// whatever counts were already posted in place.
+    pub unsafe fn post_n(&self, n: u32) -> std::io::Result<()> {
+        for _ in 0..n {
+            self.post()?;
+        }
+        Ok(())
+    }
+
+    /// Waits on the semaphore `n` times, decrementing its count by `n`.
+    ///
+    /// Acquiring `n` counts is not atomic: if the k-th wait fails, the first
+    /// `k - 1` counts have already been consumed. When `rollback` is `true`,
+    /// a failure posts back the counts already acquired so the overall
+    /// operation is all-or-nothing; when `false`, the partial acquisition is
+    /// left in place and it is up to the caller to reconcile it.
+    pub unsafe fn wait_n(&self, n: u32, rollback: bool) -> std::io::Result<()> {
+        for i in 0..n {
+            if let Err(e) = self.wait() {
+                if rollback {
+                    let _ = self.post_n(i);
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Blocks until the semaphore can be acquired, then returns a
+    /// [`SemaphoreGuard`] that releases it again on drop.
+    pub unsafe fn acquire(&self) -> std::io::Result<SemaphoreGuard<'_>> {
+        self.wait()?;
+        Ok(SemaphoreGuard { sem: self })
+    }
+
+    /// Attempts to acquire the semaphore without blocking, returning a
+    /// [`SemaphoreGuard`] on success or `None` if it would have blocked.
+    pub unsafe fn try_acquire(&self) -> std::io::Result<Option<SemaphoreGuard<'_>>> {
+        if self.try_wait()? {
+            Ok(Some(SemaphoreGuard { sem: self }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns a future that resolves once the semaphore has been acquired.
+    ///
+    /// Not part of the public API yet: each poll performs a non-blocking
+    /// [`NamedSemaphore::try_wait`] and, if it would block, busy-spins by
+    /// re-waking itself immediately (see the `NOTE` on
+    /// [`AcquireFuture::poll`]) rather than registering for a real wakeup.
+    /// That's fine for the in-crate tests driving it with a no-op waker, but
+    /// it would peg a core on a real executor, so it stays `pub(crate)` until
+    /// there's a genuine park/yield hook.
+    pub(crate) fn acquire_async(&self) -> AcquireFuture<'_> {
+        AcquireFuture {
+            sem: self,
+            deadline: None,
+        }
+    }
+
+    /// Like [`NamedSemaphore::acquire_async`], but gives up once `timeout`
+    /// has elapsed, resolving to an `Err` with [`std::io::ErrorKind::TimedOut`].
+    pub(crate) fn acquire_async_timeout(&self, timeout: Duration) -> AcquireFuture<'_> {
+        AcquireFuture {
+            sem: self,
+            deadline: Some(Instant::now() + timeout),
+        }
+    }
+
     pub unsafe fn get_value(&self) -> std::io::Result<i32> {
         let mut val: i32 = 0;
         let res = libc::sem_getvalue(self.raw, &mut val);
@@ -105,6 +242,32 @@ impl NamedSemaphore {
         }
         Ok(val)
     }
+
+    /// Removes a named semaphore from the filesystem, identified by `name`.
+    ///
+    /// This does not affect handles that already have the semaphore open via
+    /// `sem_open`; the semaphore is only destroyed once every open handle has
+    /// been closed. Use [`NamedSemaphore::close_and_unlink`] to unlink the
+    /// semaphore this handle was created or opened with.
+    pub unsafe fn unlink(name: &str) -> std::io::Result<()> {
+        let name = CString::new(name.as_bytes())?;
+        let res = libc::sem_unlink(name.as_ptr());
+        if res == -1 {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Closes this handle and unlinks the semaphore it was created or opened
+    /// with, removing it from the filesystem.
+    pub unsafe fn close_and_unlink(self) -> std::io::Result<()> {
+        let name = self.name.clone();
+        drop(self);
+        let name = name
+            .to_str()
+            .map_err(|e| Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Self::unlink(name)
+    }
 }
 
 impl Drop for NamedSemaphore {
@@ -114,6 +277,56 @@ impl Drop for NamedSemaphore {
     }
 }
 
+/// An RAII permit obtained from [`NamedSemaphore::acquire`] or
+/// [`NamedSemaphore::try_acquire`]. The semaphore is posted back
+/// automatically when the guard is dropped, so a critical section cannot
+/// leak a permit on early return or panic.
+pub struct SemaphoreGuard<'a> {
+    sem: &'a NamedSemaphore,
+}
+
+impl Drop for SemaphoreGuard<'_> {
+    fn drop(&mut self) {
+        // Result is ignored
+        let _ = unsafe { self.sem.post() };
+    }
+}
+
+/// A future produced by [`NamedSemaphore::acquire_async`] or
+/// [`NamedSemaphore::acquire_async_timeout`] that resolves once the
+/// semaphore has been acquired.
+pub(crate) struct AcquireFuture<'a> {
+    sem: &'a NamedSemaphore,
+    deadline: Option<Instant>,
+}
+
+impl<'a> Future for AcquireFuture<'a> {
+    type Output = std::io::Result<SemaphoreGuard<'a>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match unsafe { self.sem.try_wait() } {
+            Ok(true) => Poll::Ready(Ok(SemaphoreGuard { sem: self.sem })),
+            Ok(false) => {
+                if let Some(deadline) = self.deadline {
+                    if Instant::now() >= deadline {
+                        return Poll::Ready(Err(Error::from(std::io::ErrorKind::TimedOut)));
+                    }
+                }
+                // NOTE: this is an unbounded busy-spin, not a real backoff —
+                // we re-wake on every empty poll, so a single-threaded
+                // executor with nothing else to run will hot-loop this task
+                // until the semaphore is available (or the deadline elapses).
+                // There's no pluggable park/yield hook yet, which is why
+                // `AcquireFuture` and the methods that produce it stay
+                // `pub(crate)` rather than being exposed on the public API.
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Err(e) => Poll::Ready(Err(e)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,4 +391,175 @@ mod tests {
             "semaphore should have been removed"
         );
     }
+
+    #[test]
+    fn create_close_and_unlink() {
+        assert_eq!(does_sem_exist(), false, "semaphore shouldn't already exist");
+        let sem = unsafe {
+            NamedSemaphore::create(SEM_NAME, SemFSMode::all(), 0, true)
+                .expect("couldn't create semaphore")
+        };
+        assert_eq!(does_sem_exist(), true, "semaphore was not created");
+
+        let res = unsafe { sem.close_and_unlink() };
+        assert!(res.is_ok());
+        assert_eq!(
+            does_sem_exist(),
+            false,
+            "semaphore should have been unlinked"
+        );
+    }
+
+    #[test]
+    fn try_wait_would_block() {
+        let sem = unsafe {
+            NamedSemaphore::create("TEST_SEM_TRY_WAIT_WOULD_BLOCK", SemFSMode::all(), 0, true)
+                .expect("couldn't create semaphore")
+        };
+
+        let acquired = unsafe { sem.try_wait() };
+        assert_eq!(acquired.unwrap(), false);
+
+        let _ = unsafe { sem.close_and_unlink() };
+    }
+
+    #[test]
+    fn try_wait_succeeds() {
+        let sem = unsafe {
+            NamedSemaphore::create("TEST_SEM_TRY_WAIT_SUCCEEDS", SemFSMode::all(), 1, true)
+                .expect("couldn't create semaphore")
+        };
+
+        let acquired = unsafe { sem.try_wait() };
+        assert_eq!(acquired.unwrap(), true);
+
+        let _ = unsafe { sem.close_and_unlink() };
+    }
+
+    #[test]
+    fn timed_wait_times_out() {
+        let sem = unsafe {
+            NamedSemaphore::create("TEST_SEM_TIMED_WAIT_TIMES_OUT", SemFSMode::all(), 0, true)
+                .expect("couldn't create semaphore")
+        };
+
+        let acquired = unsafe { sem.timed_wait(std::time::Duration::from_millis(50)) };
+        assert_eq!(acquired.unwrap(), false);
+
+        let _ = unsafe { sem.close_and_unlink() };
+    }
+
+    #[test]
+    fn acquire_releases_on_drop() {
+        let sem = unsafe {
+            NamedSemaphore::create("TEST_SEM_ACQUIRE_RELEASES_ON_DROP", SemFSMode::all(), 1, true)
+                .expect("couldn't create semaphore")
+        };
+
+        {
+            let _guard = unsafe { sem.acquire().expect("couldn't acquire semaphore") };
+            let val = unsafe { sem.get_value().unwrap() };
+            assert_eq!(val, 0);
+        }
+        let val = unsafe { sem.get_value().unwrap() };
+        assert_eq!(val, 1);
+
+        let _ = unsafe { sem.close_and_unlink() };
+    }
+
+    #[test]
+    fn try_acquire_returns_none_when_empty() {
+        let sem = unsafe {
+            NamedSemaphore::create(
+                "TEST_SEM_TRY_ACQUIRE_RETURNS_NONE",
+                SemFSMode::all(),
+                0,
+                true,
+            )
+            .expect("couldn't create semaphore")
+        };
+
+        let guard = unsafe { sem.try_acquire().expect("try_acquire shouldn't fail") };
+        assert!(guard.is_none());
+        drop(guard);
+
+        let _ = unsafe { sem.close_and_unlink() };
+    }
+
+    #[test]
+    fn post_n_and_wait_n() {
+        let sem = unsafe {
+            NamedSemaphore::create("TEST_SEM_POST_N_AND_WAIT_N", SemFSMode::all(), 0, true)
+                .expect("couldn't create semaphore")
+        };
+
+        unsafe { sem.post_n(3).expect("couldn't post_n") };
+        let val = unsafe { sem.get_value().unwrap() };
+        assert_eq!(val, 3);
+
+        unsafe { sem.wait_n(3, false).expect("couldn't wait_n") };
+        let val = unsafe { sem.get_value().unwrap() };
+        assert_eq!(val, 0);
+
+        let _ = unsafe { sem.close_and_unlink() };
+    }
+
+    fn noop_waker() -> std::task::Waker {
+        fn clone(_: *const ()) -> std::task::RawWaker {
+            raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        fn raw_waker() -> std::task::RawWaker {
+            static VTABLE: std::task::RawWakerVTable =
+                std::task::RawWakerVTable::new(clone, noop, noop, noop);
+            std::task::RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        unsafe { std::task::Waker::from_raw(raw_waker()) }
+    }
+
+    fn poll_to_completion<F: std::future::Future>(mut fut: F) -> F::Output {
+        let waker = noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        let mut fut = unsafe { std::pin::Pin::new_unchecked(&mut fut) };
+        loop {
+            if let std::task::Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                return out;
+            }
+        }
+    }
+
+    #[test]
+    fn acquire_async_resolves_when_available() {
+        let sem = unsafe {
+            NamedSemaphore::create(
+                "TEST_SEM_ACQUIRE_ASYNC_RESOLVES",
+                SemFSMode::all(),
+                1,
+                true,
+            )
+            .expect("couldn't create semaphore")
+        };
+
+        let guard = poll_to_completion(sem.acquire_async());
+        assert!(guard.is_ok());
+        drop(guard);
+
+        let _ = unsafe { sem.close_and_unlink() };
+    }
+
+    #[test]
+    fn acquire_async_timeout_elapses() {
+        let sem = unsafe {
+            NamedSemaphore::create("TEST_SEM_ACQUIRE_ASYNC_TIMEOUT", SemFSMode::all(), 0, true)
+                .expect("couldn't create semaphore")
+        };
+
+        let kind = match poll_to_completion(sem.acquire_async_timeout(Duration::from_millis(50))) {
+            Ok(_) => panic!("acquire_async_timeout should have timed out"),
+            Err(e) => e.kind(),
+        };
+        assert_eq!(kind, std::io::ErrorKind::TimedOut);
+
+        let _ = unsafe { sem.close_and_unlink() };
+    }
 }
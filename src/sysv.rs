@@ -0,0 +1,234 @@
+use libc::{GETVAL, IPC_CREAT, IPC_RMID, SETVAL};
+use std::fmt;
+
+/// Error returned by [`SysVSemaphore`] operations.
+#[derive(Debug)]
+pub enum SysVError {
+    /// The semaphore set was removed (e.g. via [`SysVSemaphore::remove`] from
+    /// another process) while this handle still referred to it.
+    ///
+    /// On Linux this is reported as `EIDRM` when the caller was already
+    /// blocked inside `semop` and another process removed the set out from
+    /// under it, or as `EINVAL` when the caller issues a new `semctl`/`semop`
+    /// call after the set is already gone. Because a bare `EINVAL` is also
+    /// what a bad argument (an out-of-range `sem_num`, a mismatched `nsems`,
+    /// an empty `op` slice, ...) looks like, [`SysVSemaphore::get_value`],
+    /// [`SysVSemaphore::set_value`] and [`SysVSemaphore::remove`] only report
+    /// `Removed` for `EINVAL` against a `semid` this handle already obtained
+    /// successfully. For `get_value` and `set_value` an out-of-range
+    /// `sem_num` also yields `EINVAL` and is therefore reported as `Removed`
+    /// as well.
+    /// [`SysVSemaphore::get`] and [`SysVSemaphore::op`] cannot make that
+    /// distinction and surface `EINVAL` as [`SysVError::Io`] instead.
+    Removed,
+    /// Any other OS-level failure, as reported by `errno`.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for SysVError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SysVError::Removed => write!(f, "semaphore set was removed"),
+            SysVError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for SysVError {}
+
+impl From<std::io::Error> for SysVError {
+    fn from(e: std::io::Error) -> Self {
+        SysVError::Io(e)
+    }
+}
+
+/// Maps the last OS error to a [`SysVError`], treating only `EIDRM` as
+/// [`SysVError::Removed`].
+///
+/// Use this for calls whose other arguments can themselves be invalid (e.g.
+/// [`SysVSemaphore::get`]'s `nsems`, or [`SysVSemaphore::op`]'s `ops`), where
+/// `EINVAL` is ambiguous between "the set is gone" and "the argument is bad".
+fn from_last_os_error() -> SysVError {
+    let err = std::io::Error::last_os_error();
+    if err.raw_os_error() == Some(libc::EIDRM) {
+        SysVError::Removed
+    } else {
+        SysVError::Io(err)
+    }
+}
+
+/// Like [`from_last_os_error`], but also treats `EINVAL` as
+/// [`SysVError::Removed`].
+///
+/// Only call this after a `semid` this handle already obtained successfully.
+/// Any other argument that independently causes `EINVAL` (such as an
+/// out-of-range `sem_num` for `GETVAL`/`SETVAL`) is reported as
+/// [`SysVError::Removed`] too.
+fn from_last_os_error_assume_valid_id() -> SysVError {
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::EIDRM) | Some(libc::EINVAL) => SysVError::Removed,
+        _ => SysVError::Io(err),
+    }
+}
+
+pub type SysVResult<T> = Result<T, SysVError>;
+
+/// A single reserve/release operation to be submitted to [`SysVSemaphore::op`].
+///
+/// Mirrors a `libc::sembuf` entry: a negative `sem_op` reserves (blocking
+/// until available), a positive `sem_op` releases, and `0` waits for the
+/// semaphore to reach zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SemOp {
+    pub sem_num: u16,
+    pub sem_op: i16,
+    pub sem_flg: i16,
+}
+
+/// A System V semaphore set, backed by `semget`/`semop`/`semctl`.
+///
+/// Unlike [`crate::NamedSemaphore`], a `SysVSemaphore` can hold several
+/// counters (a "set") and submit a group of reserve/release operations to
+/// [`SysVSemaphore::op`] atomically: either all operations in the group
+/// succeed, or none do.
+pub struct SysVSemaphore {
+    semid: i32,
+}
+
+impl SysVSemaphore {
+    /// Gets (creating if necessary) the semaphore set identified by `key`,
+    /// with `nsems` semaphores in the set.
+    ///
+    /// Wraps `semget` with `IPC_CREAT` added to `flags`.
+    pub unsafe fn get(key: i32, nsems: i32, flags: i32) -> SysVResult<Self> {
+        let semid = libc::semget(key, nsems, flags | IPC_CREAT);
+        if semid == -1 {
+            return Err(from_last_os_error());
+        }
+        Ok(Self { semid })
+    }
+
+    /// Sets the value of the semaphore numbered `sem_num` in the set.
+    ///
+    /// Wraps `semctl` with the `SETVAL` command. Callers are responsible for
+    /// keeping `sem_num` within the `nsems` this set was created with; an
+    /// out-of-range `sem_num` is also reported as [`SysVError::Removed`],
+    /// see its docs.
+    pub unsafe fn set_value(&self, sem_num: u16, val: i32) -> SysVResult<()> {
+        let res = libc::semctl(self.semid, sem_num as i32, SETVAL, val);
+        if res == -1 {
+            return Err(from_last_os_error_assume_valid_id());
+        }
+        Ok(())
+    }
+
+    /// Gets the value of the semaphore numbered `sem_num` in the set.
+    ///
+    /// Wraps `semctl` with the `GETVAL` command. Callers are responsible for
+    /// keeping `sem_num` within the `nsems` this set was created with; an
+    /// out-of-range `sem_num` is also reported as [`SysVError::Removed`],
+    /// see its docs.
+    pub unsafe fn get_value(&self, sem_num: u16) -> SysVResult<i32> {
+        let res = libc::semctl(self.semid, sem_num as i32, GETVAL);
+        if res == -1 {
+            return Err(from_last_os_error_assume_valid_id());
+        }
+        Ok(res)
+    }
+
+    /// Submits a group of operations to the set via `semop`. The operations
+    /// are applied atomically: either all of them succeed, or none do and
+    /// the set is left unchanged.
+    ///
+    /// An empty `ops` also causes `semop` to fail with `EINVAL`, the same
+    /// code used for a removed set, so this
+    /// method cannot reliably report [`SysVError::Removed`] and surfaces
+    /// `EINVAL` as [`SysVError::Io`] instead.
+    pub unsafe fn op(&self, ops: &[SemOp]) -> SysVResult<()> {
+        let mut sembufs: Vec<libc::sembuf> = ops
+            .iter()
+            .map(|op| libc::sembuf {
+                sem_num: op.sem_num,
+                sem_op: op.sem_op,
+                sem_flg: op.sem_flg,
+            })
+            .collect();
+
+        let res = libc::semop(self.semid, sembufs.as_mut_ptr(), sembufs.len());
+        if res == -1 {
+            return Err(from_last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Removes the semaphore set from the system.
+    ///
+    /// Wraps `semctl` with the `IPC_RMID` command. Any other handle still
+    /// referring to this set will observe [`SysVError::Removed`] on its next
+    /// operation; calling `remove` again on an already-removed set also
+    /// reports [`SysVError::Removed`], since its `semid` no longer resolves.
+    pub unsafe fn remove(&self) -> SysVResult<()> {
+        let res = libc::semctl(self.semid, 0, IPC_RMID, 0);
+        if res == -1 {
+            return Err(from_last_os_error_assume_valid_id());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEM_KEY: i32 = 0x5afe_5eed;
+
+    #[test]
+    fn get_set_and_get_value() {
+        let sem =
+            unsafe { SysVSemaphore::get(SEM_KEY, 1, 0o600).expect("couldn't get semaphore set") };
+
+        unsafe { sem.set_value(0, 3).expect("couldn't set value") };
+        let val = unsafe { sem.get_value(0).unwrap() };
+        assert_eq!(val, 3);
+
+        unsafe { sem.remove().expect("couldn't remove semaphore set") };
+    }
+
+    #[test]
+    fn op_reserves_and_releases() {
+        let sem =
+            unsafe { SysVSemaphore::get(SEM_KEY, 1, 0o600).expect("couldn't get semaphore set") };
+        unsafe { sem.set_value(0, 1).expect("couldn't set value") };
+
+        let reserve = [SemOp {
+            sem_num: 0,
+            sem_op: -1,
+            sem_flg: 0,
+        }];
+        unsafe { sem.op(&reserve).expect("couldn't reserve") };
+        let val = unsafe { sem.get_value(0).unwrap() };
+        assert_eq!(val, 0);
+
+        let release = [SemOp {
+            sem_num: 0,
+            sem_op: 1,
+            sem_flg: 0,
+        }];
+        unsafe { sem.op(&release).expect("couldn't release") };
+        let val = unsafe { sem.get_value(0).unwrap() };
+        assert_eq!(val, 1);
+
+        unsafe { sem.remove().expect("couldn't remove semaphore set") };
+    }
+
+    #[test]
+    fn operations_fail_after_removal() {
+        let sem =
+            unsafe { SysVSemaphore::get(SEM_KEY, 1, 0o600).expect("couldn't get semaphore set") };
+        unsafe { sem.remove().expect("couldn't remove semaphore set") };
+
+        let res = unsafe { sem.get_value(0) };
+        assert!(matches!(res, Err(SysVError::Removed)));
+    }
+}